@@ -0,0 +1,43 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::Param;
+use alloc::{format, string::String, vec::Vec};
+use serde::Deserialize;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Contract error definition (Solidity custom error, `type: "error"`).
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AbiError {
+	/// Error name.
+	pub name: String,
+	/// Error input parameters.
+	pub inputs: Vec<Param>,
+}
+
+impl AbiError {
+	/// Returns the error signature, e.g. `InsufficientBalance(uint256,uint256)`.
+	pub fn signature(&self) -> String {
+		let types = self.inputs.iter().map(|param| param.kind.to_string()).collect::<Vec<String>>().join(",");
+
+		format!("{}({})", self.name, types)
+	}
+
+	/// Returns the 4-byte selector identifying this error, i.e. the first four
+	/// bytes of the Keccak-256 hash of [`AbiError::signature`].
+	pub fn selector(&self) -> [u8; 4] {
+		let mut hash = [0u8; 32];
+		let mut keccak = Keccak::v256();
+		keccak.update(self.signature().as_bytes());
+		keccak.finalize(&mut hash);
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&hash[..4]);
+		selector
+	}
+}
@@ -7,13 +7,14 @@
 // except according to those terms.
 
 use crate::operation::Operation;
-use crate::{errors, Constructor, Error, Event, Function};
+use crate::{errors, AbiError, Constructor, Error, Event, Function, ParamType, Token};
 use alloc::collections::btree_map::BTreeMap;
 use alloc::collections::btree_map::Values;
 use alloc::{borrow::ToOwned, string::String, vec::Vec};
 use core::fmt;
 use core::iter::Flatten;
-use serde::de::{SeqAccess, Visitor};
+use ethereum_types::H256;
+use serde::de::{IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 #[cfg(feature = "std")]
 use std::io;
@@ -27,10 +28,27 @@ pub struct Contract {
 	pub functions: BTreeMap<String, Vec<Function>>,
 	/// Contract events, maps signature to event.
 	pub events: BTreeMap<String, Vec<Event>>,
+	/// Contract custom errors, maps name to error.
+	pub errors: BTreeMap<String, Vec<AbiError>>,
 	/// Contract has fallback function.
 	pub fallback: bool,
 	/// Contract receives.
 	pub receive: bool,
+	/// Contract creation bytecode, if loaded from a compiler artifact that
+	/// includes one (e.g. a Hardhat/Truffle build output).
+	pub bytecode: Option<Vec<u8>>,
+	/// Contract deployed (runtime) bytecode, if loaded from a compiler
+	/// artifact that includes one.
+	pub deployed_bytecode: Option<Vec<u8>>,
+	/// `function_by_selector` index, keyed by selector to `(function name,
+	/// index within functions[name])`, built once in [`Contract::from_operations`].
+	function_selectors: BTreeMap<[u8; 4], (String, usize)>,
+	/// `event_by_topic` index, keyed by topic to `(event name, index within
+	/// events[name])`, built once in [`Contract::from_operations`].
+	event_topics: BTreeMap<H256, (String, usize)>,
+	/// `find_error` index, keyed by selector to `(error name, index within
+	/// errors[name])`, built once in [`Contract::from_operations`].
+	error_selectors: BTreeMap<[u8; 4], (String, usize)>,
 }
 
 impl<'a> Deserialize<'a> for Contract {
@@ -55,15 +73,67 @@ impl<'a> Visitor<'a> for ContractVisitor {
 	where
 		A: SeqAccess<'a>,
 	{
+		let mut operations = Vec::new();
+		while let Some(operation) = seq.next_element()? {
+			operations.push(operation);
+		}
+
+		Ok(Contract::from_operations(operations))
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'a>,
+	{
+		let mut abi: Option<Vec<Operation>> = None;
+		let mut bytecode: Option<String> = None;
+		let mut deployed_bytecode: Option<String> = None;
+
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				"abi" => abi = Some(map.next_value()?),
+				"bytecode" => bytecode = map.next_value()?,
+				"deployedBytecode" => deployed_bytecode = map.next_value()?,
+				_ => {
+					map.next_value::<IgnoredAny>()?;
+				}
+			}
+		}
+
+		let abi = abi.ok_or_else(|| serde::de::Error::missing_field("abi"))?;
+		let mut result = Contract::from_operations(abi);
+		result.bytecode = bytecode.as_deref().map(decode_bytecode).transpose()?;
+		result.deployed_bytecode = deployed_bytecode.as_deref().map(decode_bytecode).transpose()?;
+
+		Ok(result)
+	}
+}
+
+/// Decodes a `0x`-prefixed (or bare) hex string into bytes, as found in the
+/// `bytecode`/`deployedBytecode` fields of a Hardhat/Truffle artifact.
+fn decode_bytecode<E: serde::de::Error>(s: &str) -> Result<Vec<u8>, E> {
+	hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(serde::de::Error::custom)
+}
+
+impl Contract {
+	/// Builds a `Contract` by sorting a flat list of ABI operations into its
+	/// constructor/functions/events/errors/fallback/receive fields.
+	fn from_operations(operations: Vec<Operation>) -> Self {
 		let mut result = Contract {
 			constructor: None,
 			functions: BTreeMap::default(),
 			events: BTreeMap::default(),
+			errors: BTreeMap::default(),
 			fallback: false,
 			receive: false,
+			bytecode: None,
+			deployed_bytecode: None,
+			function_selectors: BTreeMap::default(),
+			event_topics: BTreeMap::default(),
+			error_selectors: BTreeMap::default(),
 		};
 
-		while let Some(operation) = seq.next_element()? {
+		for operation in operations {
 			match operation {
 				Operation::Constructor(constructor) => {
 					result.constructor = Some(constructor);
@@ -74,6 +144,9 @@ impl<'a> Visitor<'a> for ContractVisitor {
 				Operation::Event(event) => {
 					result.events.entry(event.name.clone()).or_default().push(event);
 				}
+				Operation::Error(error) => {
+					result.errors.entry(error.name.clone()).or_default().push(error);
+				}
 				Operation::Fallback => {
 					result.fallback = true;
 				}
@@ -83,11 +156,27 @@ impl<'a> Visitor<'a> for ContractVisitor {
 			}
 		}
 
-		Ok(result)
+		for (name, funcs) in &result.functions {
+			for (index, func) in funcs.iter().enumerate() {
+				result.function_selectors.insert(func.short_signature(), (name.clone(), index));
+			}
+		}
+
+		for (name, events) in &result.events {
+			for (index, event) in events.iter().enumerate() {
+				result.event_topics.insert(event.signature(), (name.clone(), index));
+			}
+		}
+
+		for (name, errors) in &result.errors {
+			for (index, error) in errors.iter().enumerate() {
+				result.error_selectors.insert(error.selector(), (name.clone(), index));
+			}
+		}
+
+		result
 	}
-}
 
-impl Contract {
 	/// Loads contract from json.
 	pub fn from_str(s: &str) -> errors::Result<Self> {
 		serde_json::from_str(s).map_err(From::from)
@@ -125,6 +214,17 @@ impl Contract {
 		self.functions.get(name).ok_or_else(|| Error::InvalidName(name.to_owned()))
 	}
 
+	/// Get the contract error named `name`, the first if there are overloaded
+	/// versions of the same error.
+	pub fn error(&self, name: &str) -> errors::Result<&AbiError> {
+		self.errors.get(name).into_iter().flatten().next().ok_or_else(|| Error::InvalidName(name.to_owned()))
+	}
+
+	/// Get all contract errors named `name`.
+	pub fn errors_by_name(&self, name: &str) -> errors::Result<&Vec<AbiError>> {
+		self.errors.get(name).ok_or_else(|| Error::InvalidName(name.to_owned()))
+	}
+
 	/// Iterate over all functions of the contract in arbitrary order.
 	pub fn functions(&self) -> Functions {
 		Functions(self.functions.values().flatten())
@@ -135,10 +235,190 @@ impl Contract {
 		Events(self.events.values().flatten())
 	}
 
+	/// Iterate over all custom errors of the contract in arbitrary order.
+	pub fn errors(&self) -> Errors {
+		Errors(self.errors.values().flatten())
+	}
+
 	/// Returns true if contract has fallback
 	pub fn fallback(&self) -> bool {
 		self.fallback
 	}
+
+	/// Get the function whose 4-byte selector is `selector`, disambiguating overloaded functions.
+	pub fn function_by_selector(&self, selector: [u8; 4]) -> errors::Result<&Function> {
+		let (name, index) = self.function_selectors.get(&selector).ok_or(Error::SelectorNotFound)?;
+		self.functions.get(name).and_then(|funcs| funcs.get(*index)).ok_or(Error::SelectorNotFound)
+	}
+
+	/// Get the event whose topic-0 (the Keccak-256 hash of its signature) is `topic`, disambiguating overloaded events.
+	pub fn event_by_topic(&self, topic: H256) -> errors::Result<&Event> {
+		let (name, index) = self.event_topics.get(&topic).ok_or(Error::SelectorNotFound)?;
+		self.events.get(name).and_then(|events| events.get(*index)).ok_or(Error::SelectorNotFound)
+	}
+
+	/// Decodes a reverted call's return data into the `Token`s of the
+	/// matching custom error; the matched error itself is not returned.
+	///
+	/// `data` is expected to start with the 4-byte error selector, as returned
+	/// by a reverted call, followed by the ABI-encoded error parameters.
+	pub fn decode_error(&self, data: &[u8]) -> errors::Result<Vec<Token>> {
+		if data.len() < 4 {
+			return Err(Error::InvalidData);
+		}
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&data[..4]);
+
+		let error = self.find_error(selector)?;
+
+		crate::decode(&error.inputs.iter().map(|param| param.kind.clone()).collect::<Vec<_>>(), &data[4..])
+	}
+
+	/// Looks up a registered custom error by its 4-byte selector.
+	fn find_error(&self, selector: [u8; 4]) -> errors::Result<&AbiError> {
+		let (name, index) = self.error_selectors.get(&selector).ok_or(Error::ErrorNotFound)?;
+		self.errors.get(name).and_then(|errors| errors.get(*index)).ok_or(Error::ErrorNotFound)
+	}
+
+	/// Routes raw transaction calldata to the function it invokes and decodes
+	/// its arguments.
+	///
+	/// The first four bytes of `data` are read as a function selector and
+	/// resolved via [`Contract::function_by_selector`], so the correct
+	/// overload is picked even if several functions share a name; the
+	/// remaining bytes are decoded using that function's input parameter
+	/// types. Empty `data` (a plain value transfer, carrying no selector) is
+	/// classified instead of decoded, see [`DecodedInput`].
+	pub fn decode_input(&self, data: &[u8]) -> errors::Result<DecodedInput> {
+		if data.is_empty() {
+			return Ok(if self.receive {
+				DecodedInput::Receive
+			} else if self.fallback {
+				DecodedInput::Fallback
+			} else if self.constructor.is_some() {
+				DecodedInput::Constructor
+			} else {
+				DecodedInput::Unsupported
+			});
+		}
+
+		if data.len() < 4 {
+			return Err(Error::InvalidData);
+		}
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&data[..4]);
+
+		let function = self.function_by_selector(selector)?;
+		let tokens = function.decode_input(&data[4..])?;
+
+		Ok(DecodedInput::Function(function, tokens))
+	}
+
+	/// Decodes the return data of a failed call into a human-readable revert
+	/// reason.
+	///
+	/// Checks the 4-byte selector against the two builtin Solidity revert
+	/// signatures, `Error(string)` and `Panic(uint256)`, before falling back
+	/// to the contract's registered custom errors (see
+	/// [`Contract::decode_error`]). Empty `data` yields [`RevertReason::Empty`].
+	pub fn decode_revert(&self, data: &[u8]) -> errors::Result<RevertReason> {
+		if data.is_empty() {
+			return Ok(RevertReason::Empty);
+		}
+
+		if data.len() < 4 {
+			return Err(Error::InvalidData);
+		}
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&data[..4]);
+
+		match selector {
+			ERROR_SELECTOR => match crate::decode(&[ParamType::String], &data[4..])?.into_iter().next() {
+				Some(Token::String(message)) => Ok(RevertReason::Error(message)),
+				_ => Err(Error::InvalidData),
+			},
+			PANIC_SELECTOR => match crate::decode(&[ParamType::Uint(256)], &data[4..])?.into_iter().next() {
+				Some(Token::Uint(code)) => Ok(RevertReason::Panic(code.low_u64())),
+				_ => Err(Error::InvalidData),
+			},
+			_ => {
+				let error = self.find_error(selector)?;
+				let tokens =
+					crate::decode(&error.inputs.iter().map(|param| param.kind.clone()).collect::<Vec<_>>(), &data[4..])?;
+
+				Ok(RevertReason::Custom { error, tokens })
+			}
+		}
+	}
+}
+
+/// Selector of the canonical `Error(string)` revert reason.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of the canonical `Panic(uint256)` revert reason.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// A decoded reason for a failed contract call, see [`Contract::decode_revert`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RevertReason<'a> {
+	/// The canonical `Error(string)` revert reason.
+	Error(String),
+	/// The canonical `Panic(uint256)` revert reason, decoded to its numeric
+	/// code. See [`RevertReason::panic_message`] for its meaning.
+	Panic(u64),
+	/// A registered custom error, decoded with its definition and arguments.
+	Custom {
+		/// The matched custom error definition.
+		error: &'a AbiError,
+		/// The error's decoded arguments.
+		tokens: Vec<Token>,
+	},
+	/// No revert data was returned.
+	Empty,
+}
+
+impl<'a> RevertReason<'a> {
+	/// Returns the human-readable meaning of a `Panic(uint256)` code, as
+	/// assigned by the Solidity compiler, or `None` if the code is not one of
+	/// the known built-in panics.
+	pub fn panic_message(code: u64) -> Option<&'static str> {
+		Some(match code {
+			0x01 => "assertion failed",
+			0x11 => "arithmetic operation overflowed or underflowed",
+			0x12 => "division or modulo by zero",
+			0x21 => "value out of range for an enum type",
+			0x22 => "incorrectly encoded storage byte array",
+			0x31 => "pop on an empty array",
+			0x32 => "array index out of bounds",
+			0x41 => "allocated too much memory or created an array too large",
+			0x51 => "called a zero-initialized variable of internal function type",
+			_ => return None,
+		})
+	}
+}
+
+/// The result of routing raw transaction calldata via [`Contract::decode_input`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodedInput<'a> {
+	/// Calldata matched a known function selector; holds the matched
+	/// function and its decoded arguments.
+	Function(&'a Function, Vec<Token>),
+	/// Empty calldata and the contract declares a `receive` function.
+	Receive,
+	/// Empty calldata and the contract declares a `fallback` function (and no
+	/// `receive`).
+	Fallback,
+	/// Empty calldata, no `receive`/`fallback`, and the contract declares a
+	/// constructor; this is only meaningful for a deployment transaction,
+	/// since a constructor cannot be called once the contract is deployed.
+	Constructor,
+	/// Empty calldata and the contract declares neither `receive`,
+	/// `fallback`, nor a constructor. Such a call carries no selector to
+	/// route and would simply revert on-chain.
+	Unsupported,
 }
 
 /// Contract functions iterator.
@@ -162,3 +442,206 @@ impl<'a> Iterator for Events<'a> {
 		self.0.next()
 	}
 }
+
+/// Contract errors iterator.
+pub struct Errors<'a>(Flatten<Values<'a, String, Vec<AbiError>>>);
+
+impl<'a> Iterator for Errors<'a> {
+	type Item = &'a AbiError;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn token_uint(value: u64) -> Token {
+		Token::Uint(value.into())
+	}
+
+	#[test]
+	fn decodes_registered_custom_error() {
+		let contract = Contract::from_str(
+			r#"[{"type":"error","name":"InsufficientBalance","inputs":[{"name":"available","type":"uint256"},{"name":"required","type":"uint256"}]}]"#,
+		)
+		.unwrap();
+
+		let error = contract.error("InsufficientBalance").unwrap();
+		let mut data = error.selector().to_vec();
+		data.extend(crate::encode(&[token_uint(1), token_uint(2)]));
+
+		let tokens = contract.decode_error(&data).unwrap();
+		assert_eq!(tokens, vec![token_uint(1), token_uint(2)]);
+	}
+
+	#[test]
+	fn decode_error_rejects_unknown_selector() {
+		let contract = Contract::from_str("[]").unwrap();
+
+		assert!(matches!(contract.decode_error(&[0, 0, 0, 0]), Err(Error::ErrorNotFound)));
+	}
+
+	#[test]
+	fn function_by_selector_disambiguates_overloads() {
+		let contract = Contract::from_str(
+			r#"[
+				{"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"},
+				{"type":"function","name":"transfer","inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"}
+			]"#,
+		)
+		.unwrap();
+
+		let overloads = contract.functions_by_name("transfer").unwrap();
+		let two_arg = overloads.iter().find(|f| f.inputs.len() == 2).unwrap();
+		let three_arg = overloads.iter().find(|f| f.inputs.len() == 3).unwrap();
+
+		assert_eq!(contract.function_by_selector(two_arg.short_signature()).unwrap().inputs.len(), 2);
+		assert_eq!(contract.function_by_selector(three_arg.short_signature()).unwrap().inputs.len(), 3);
+	}
+
+	#[test]
+	fn event_by_topic_disambiguates_overloads() {
+		let contract = Contract::from_str(
+			r#"[
+				{"type":"event","name":"Transfer","inputs":[{"name":"to","type":"address","indexed":true},{"name":"amount","type":"uint256","indexed":false}],"anonymous":false},
+				{"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"amount","type":"uint256","indexed":false}],"anonymous":false}
+			]"#,
+		)
+		.unwrap();
+
+		let overloads = contract.events_by_name("Transfer").unwrap();
+		let two_arg = overloads.iter().find(|e| e.inputs.len() == 2).unwrap();
+		let three_arg = overloads.iter().find(|e| e.inputs.len() == 3).unwrap();
+
+		assert_eq!(contract.event_by_topic(two_arg.signature()).unwrap().inputs.len(), 2);
+		assert_eq!(contract.event_by_topic(three_arg.signature()).unwrap().inputs.len(), 3);
+	}
+
+	#[test]
+	fn function_by_selector_index_is_built_at_construction() {
+		let contract = Contract::from_str(
+			r#"[{"type":"function","name":"foo","inputs":[],"outputs":[],"stateMutability":"view"}]"#,
+		)
+		.unwrap();
+		let selector = contract.function("foo").unwrap().short_signature();
+
+		assert_eq!(contract.function_selectors.len(), 1);
+		assert!(contract.function_by_selector(selector).is_ok());
+	}
+
+	#[test]
+	fn find_error_index_is_built_at_construction() {
+		let contract = Contract::from_str(r#"[{"type":"error","name":"Oops","inputs":[]}]"#).unwrap();
+		let selector = contract.error("Oops").unwrap().selector();
+
+		assert_eq!(contract.error_selectors.len(), 1);
+		assert!(contract.decode_error(&selector).is_ok());
+	}
+
+	#[test]
+	fn decode_input_routes_to_matching_function() {
+		let contract = Contract::from_str(
+			r#"[{"type":"function","name":"foo","inputs":[{"name":"x","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"}]"#,
+		)
+		.unwrap();
+		let function = contract.function("foo").unwrap();
+
+		let mut data = function.short_signature().to_vec();
+		data.extend(crate::encode(&[token_uint(42)]));
+
+		match contract.decode_input(&data).unwrap() {
+			DecodedInput::Function(function, tokens) => {
+				assert_eq!(function.name, "foo");
+				assert_eq!(tokens, vec![token_uint(42)]);
+			}
+			other => panic!("unexpected {:?}", other),
+		}
+	}
+
+	#[test]
+	fn decode_input_classifies_empty_calldata() {
+		let with_receive = Contract::from_str(r#"[{"type":"receive"}]"#).unwrap();
+		assert_eq!(with_receive.decode_input(&[]).unwrap(), DecodedInput::Receive);
+
+		let with_fallback = Contract::from_str(r#"[{"type":"fallback"}]"#).unwrap();
+		assert_eq!(with_fallback.decode_input(&[]).unwrap(), DecodedInput::Fallback);
+
+		let with_constructor =
+			Contract::from_str(r#"[{"type":"constructor","inputs":[],"stateMutability":"nonpayable"}]"#).unwrap();
+		assert_eq!(with_constructor.decode_input(&[]).unwrap(), DecodedInput::Constructor);
+
+		let bare = Contract::from_str("[]").unwrap();
+		assert_eq!(bare.decode_input(&[]).unwrap(), DecodedInput::Unsupported);
+	}
+
+	#[test]
+	fn loads_hardhat_artifact_object() {
+		let contract = Contract::from_str(
+			r#"{
+				"contractName": "Foo",
+				"abi": [{"type":"function","name":"foo","inputs":[],"outputs":[],"stateMutability":"view"}],
+				"bytecode": "0x1234",
+				"deployedBytecode": "0xabcd"
+			}"#,
+		)
+		.unwrap();
+
+		assert!(contract.function("foo").is_ok());
+		assert_eq!(contract.bytecode, Some(vec![0x12, 0x34]));
+		assert_eq!(contract.deployed_bytecode, Some(vec![0xab, 0xcd]));
+	}
+
+	#[test]
+	fn loads_artifact_object_without_bytecode_fields() {
+		let contract = Contract::from_str(r#"{"abi": []}"#).unwrap();
+
+		assert_eq!(contract.bytecode, None);
+		assert_eq!(contract.deployed_bytecode, None);
+	}
+
+	#[test]
+	fn decode_revert_decodes_builtin_error_string() {
+		let contract = Contract::from_str("[]").unwrap();
+
+		let mut data = ERROR_SELECTOR.to_vec();
+		data.extend(crate::encode(&[Token::String("oops".into())]));
+
+		assert_eq!(contract.decode_revert(&data).unwrap(), RevertReason::Error("oops".into()));
+	}
+
+	#[test]
+	fn decode_revert_decodes_builtin_panic_code() {
+		let contract = Contract::from_str("[]").unwrap();
+
+		let mut data = PANIC_SELECTOR.to_vec();
+		data.extend(crate::encode(&[token_uint(0x11)]));
+
+		assert_eq!(contract.decode_revert(&data).unwrap(), RevertReason::Panic(0x11));
+		assert_eq!(RevertReason::panic_message(0x11), Some("arithmetic operation overflowed or underflowed"));
+	}
+
+	#[test]
+	fn decode_revert_falls_back_to_custom_error() {
+		let contract = Contract::from_str(r#"[{"type":"error","name":"Oops","inputs":[]}]"#).unwrap();
+		let error = contract.error("Oops").unwrap();
+
+		let data = error.selector().to_vec();
+
+		match contract.decode_revert(&data).unwrap() {
+			RevertReason::Custom { error, tokens } => {
+				assert_eq!(error.name, "Oops");
+				assert!(tokens.is_empty());
+			}
+			other => panic!("unexpected {:?}", other),
+		}
+	}
+
+	#[test]
+	fn decode_revert_handles_empty_data() {
+		let contract = Contract::from_str("[]").unwrap();
+		assert_eq!(contract.decode_revert(&[]).unwrap(), RevertReason::Empty);
+	}
+}
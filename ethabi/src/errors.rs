@@ -0,0 +1,53 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use alloc::string::String;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+/// Contract ABI result type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Errors that can occur building or using a contract ABI.
+#[derive(Debug)]
+pub enum Error {
+	/// No function, event or error by that name was found.
+	InvalidName(String),
+	/// The provided data is too short, or otherwise the wrong shape, for the
+	/// parameter types it is being decoded against.
+	InvalidData,
+	/// No function/event overload matches the given selector/topic.
+	SelectorNotFound,
+	/// No registered custom error matches the given selector.
+	ErrorNotFound,
+	/// A `serde_json` parsing error.
+	SerdeJson(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::InvalidName(name) => write!(f, "invalid name: {}", name),
+			Error::InvalidData => write!(f, "invalid data"),
+			Error::SelectorNotFound => write!(f, "no function/event overload matches the given selector/topic"),
+			Error::ErrorNotFound => write!(f, "no registered custom error matches the given selector"),
+			Error::SerdeJson(err) => write!(f, "{}", err),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl StdError for Error {}
+
+impl From<serde_json::Error> for Error {
+	fn from(err: serde_json::Error) -> Self {
+		Error::SerdeJson(err)
+	}
+}
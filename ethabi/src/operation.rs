@@ -0,0 +1,61 @@
+// Copyright 2015-2020 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{AbiError, Constructor, Event, Function};
+use alloc::string::String;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// A single entry of an ABI definition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+	/// Contract constructor.
+	Constructor(Constructor),
+	/// Contract function.
+	Function(Function),
+	/// Contract event.
+	Event(Event),
+	/// Contract custom error.
+	Error(AbiError),
+	/// Contract fallback function.
+	Fallback,
+	/// Contract receive function.
+	Receive,
+}
+
+impl<'a> Deserialize<'a> for Operation {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'a>,
+	{
+		#[derive(Deserialize)]
+		struct Abi {
+			#[serde(rename = "type")]
+			type_field: Option<String>,
+			#[serde(flatten)]
+			rest: Value,
+		}
+
+		let abi: Abi = Deserialize::deserialize(deserializer)?;
+		let rest = abi.rest;
+
+		match abi.type_field.as_deref() {
+			Some("constructor") => {
+				Ok(Operation::Constructor(Constructor::deserialize(rest).map_err(serde::de::Error::custom)?))
+			}
+			Some("fallback") => Ok(Operation::Fallback),
+			Some("receive") => Ok(Operation::Receive),
+			Some("function") | None => {
+				Ok(Operation::Function(Function::deserialize(rest).map_err(serde::de::Error::custom)?))
+			}
+			Some("event") => Ok(Operation::Event(Event::deserialize(rest).map_err(serde::de::Error::custom)?)),
+			Some("error") => Ok(Operation::Error(AbiError::deserialize(rest).map_err(serde::de::Error::custom)?)),
+			_ => Err(serde::de::Error::custom("Invalid operation type.")),
+		}
+	}
+}